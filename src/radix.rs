@@ -1,11 +1,14 @@
 use std::error::Error;
 use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use ibig::UBig;
 
 /// Error type for radix conversions.
 #[derive(Debug, PartialEq)]
 pub enum RadixError {
     UnableToParse(String, u32),
-    UnableToFormat(u64, u32),
+    UnableToFormat(String, u32),
 }
 
 impl Error for RadixError {}
@@ -44,6 +47,31 @@ pub fn strip_prefix(s: &str) -> (&str, &str) {
     }
 }
 
+/// Splits a numeral string on a leading sign character, if any.
+///
+/// Returns `true` along with the remainder of `s` if `s` starts with `-`,
+/// `false` and the remainder if it starts with `+`, and `false` with all of
+/// `s` otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use cvtr::radix;
+///
+/// assert_eq!(radix::strip_sign("-a"), (true, "a"));
+/// assert_eq!(radix::strip_sign("+a"), (false, "a"));
+/// assert_eq!(radix::strip_sign("a"), (false, "a"));
+/// ```
+pub fn strip_sign(s: &str) -> (bool, &str) {
+    if let Some(rest) = s.strip_prefix('-') {
+        (true, rest)
+    } else if let Some(rest) = s.strip_prefix('+') {
+        (false, rest)
+    } else {
+        (false, s)
+    }
+}
+
 /// Returns the radix for a given numeral `prefix`. In the case of an invalid
 /// prefix, `None` is returned.
 ///
@@ -77,23 +105,56 @@ pub fn detect(prefix: &str) -> Option<u32> {
 /// # Errors
 ///
 /// Returns an `Err` if attempting to format using an unsupported `radix`.
-/// Currently only 2, 5, 10, and 16 are supported as values for `radix`.
+/// `radix` must be between 2 and 36 inclusive.
 ///
 /// # Examples
 ///
 /// ```
+/// use ibig::ubig;
 /// use cvtr::radix;
 ///
-/// assert_eq!(radix::format(18, 16), Ok(String::from("12")));
+/// assert_eq!(radix::format(&ubig!(18), 16), Ok(String::from("12")));
 /// ```
-pub fn format(n: u64, radix: u32) -> Result<String, RadixError> {
-    match radix {
-        2 => Ok(format!("{:b}", n)),
-        8 => Ok(format!("{:o}", n)),
-        10 => Ok(format!("{}", n)),
-        16 => Ok(format!("{:x}", n)),
-        _ => Err(RadixError::UnableToFormat(n, radix)),
+pub fn format(n: &UBig, radix: u32) -> Result<String, RadixError> {
+    if !(2..=36).contains(&radix) {
+        return Err(RadixError::UnableToFormat(n.to_string(), radix));
     }
+    Ok(n.in_radix(radix).to_string())
+}
+
+/// Formats `n` as its fixed-width two's-complement bit pattern in a given
+/// `radix`, using `bits` as the width.
+///
+/// `n` is reinterpreted as an unsigned `bits`-wide bit pattern (truncating
+/// any bits beyond the requested width), so e.g. decimal `-1` with
+/// `bits = 8` renders as `ff` in hex. Binary and hex output is zero-padded
+/// to the natural digit count for `bits` bits.
+///
+/// # Errors
+///
+/// Returns an `Err` if attempting to format using an unsupported `radix`,
+/// or if `bits` is `0` or greater than `128`.
+///
+/// # Examples
+///
+/// ```
+/// use cvtr::radix;
+///
+/// assert_eq!(radix::format_twos_complement(-1, 16, 8), Ok(String::from("ff")));
+/// ```
+pub fn format_twos_complement(n: i128, radix: u32, bits: u32) -> Result<String, RadixError> {
+    if bits == 0 || bits > 128 {
+        return Err(RadixError::UnableToFormat(n.to_string(), radix));
+    }
+    let mask = if bits == 128 { u128::MAX } else { (1u128 << bits) - 1 };
+    let wrapped = (n as u128) & mask;
+    let formatted = format(&UBig::from(wrapped), radix)?;
+    let width = match radix {
+        2 => bits as usize,
+        16 => bits.div_ceil(4) as usize,
+        _ => return Ok(formatted),
+    };
+    Ok(format!("{:0>width$}", formatted, width = width))
 }
 
 /// Converts a numeral string, `s`, from one radix to another.
@@ -110,17 +171,28 @@ pub fn format(n: u64, radix: u32) -> Result<String, RadixError> {
 /// be because `s` is not a numeral at all, or it is not a valid numeral in the
 /// base `from_radix`.
 ///
+/// A leading `-` (or `+`) is accepted and parsed as a sign on the magnitude;
+/// the sign is re-emitted on the result using the same sign-magnitude
+/// convention, e.g. decimal `-10` converts to hex `-a`.
+///
 /// # Examples
 ///
 /// ```
 /// use cvtr::radix;
 ///
 /// assert_eq!(radix::convert("a", 16, 10), Ok(String::from("10")));
+/// assert_eq!(radix::convert("-10", 10, 16), Ok(String::from("-a")));
 /// ```
 pub fn convert(s: &str, from_radix: u32, to_radix: u32) -> Result<String, RadixError> {
-    let n = u64::from_str_radix(s, from_radix)
+    let (negative, magnitude) = strip_sign(s);
+    let n = UBig::from_str_radix(magnitude, from_radix)
         .map_err(|_| RadixError::UnableToParse(s.to_string(), from_radix))?;
-    format(n, to_radix)
+    let formatted = format(&n, to_radix)?;
+    Ok(if negative && n != UBig::from(0u32) {
+        format!("-{}", formatted)
+    } else {
+        formatted
+    })
 }
 
 /// Returns a string representation of a given radix. For example, "hex" for
@@ -135,9 +207,97 @@ pub fn as_text(radix: u32) -> String {
     }
 }
 
+/// Infers the radix of an unprefixed numeral `s` from its digit set.
+///
+/// A numeral made up entirely of decimal digits (`0`-`9`) is assumed to be
+/// decimal, matching the conventional reading of a bare number. Only once a
+/// digit forces a larger base (e.g. a hex letter) is a non-decimal radix
+/// inferred, preferring hex when it fits. Returns `None` if `s` is empty or
+/// contains a character that isn't a valid digit in any radix up to 36.
+///
+/// # Examples
+///
+/// ```
+/// use cvtr::radix;
+///
+/// assert_eq!(radix::infer("200"), Some(10));
+/// assert_eq!(radix::infer("9"), Some(10));
+/// assert_eq!(radix::infer("ff"), Some(16));
+/// ```
+pub fn infer(s: &str) -> Option<u32> {
+    let max_digit = s
+        .chars()
+        .map(|c| c.to_digit(36))
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .max()?;
+    if max_digit < 10 {
+        return Some(10);
+    }
+    let min_radix = max_digit + 1;
+    Some(if min_radix <= 16 { 16 } else { min_radix })
+}
+
+/// A validated numeric base, between 2 and 36 inclusive.
+///
+/// `Radix` implements [`FromStr`] so it can be parsed directly from a
+/// `structopt` argument, accepting textual names ("hex", "binary", "octal",
+/// "decimal"), numeral prefixes ("0x", "0b", "0"), or a plain number ("16").
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Radix(u32);
+
+impl Radix {
+    /// Returns the underlying numeric base.
+    pub fn value(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<Radix> for u32 {
+    fn from(radix: Radix) -> u32 {
+        radix.0
+    }
+}
+
+impl FromStr for Radix {
+    type Err = RadixError;
+
+    /// # Examples
+    ///
+    /// ```
+    /// use cvtr::radix::Radix;
+    ///
+    /// assert_eq!("hex".parse::<Radix>().unwrap().value(), 16);
+    /// assert_eq!("0x".parse::<Radix>().unwrap().value(), 16);
+    /// assert_eq!("16".parse::<Radix>().unwrap().value(), 16);
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let radix = match s {
+            "hex" => 16,
+            "binary" => 2,
+            "octal" => 8,
+            "decimal" => 10,
+            "0x" | "0b" | "0" => detect(s).unwrap(),
+            _ => s
+                .parse()
+                .ok()
+                .filter(|n| (2..=36).contains(n))
+                .ok_or_else(|| RadixError::UnableToParse(s.to_string(), 0))?,
+        };
+        Ok(Radix(radix))
+    }
+}
+
+impl Display for Radix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", as_text(self.0))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use ibig::ubig;
 
     #[test]
     fn strip_prefix_empty_arg() {
@@ -152,6 +312,14 @@ mod test {
         assert_eq!(strip_prefix("0xaf9"), ("0x", "af9"));
     }
 
+    #[test]
+    fn strip_sign_expected() {
+        assert_eq!(strip_sign("-a"), (true, "a"));
+        assert_eq!(strip_sign("+a"), (false, "a"));
+        assert_eq!(strip_sign("a"), (false, "a"));
+        assert_eq!(strip_sign(""), (false, ""));
+    }
+
     #[test]
     fn detect_expected() {
         assert_eq!(detect("0b"), Some(2));
@@ -163,15 +331,25 @@ mod test {
 
     #[test]
     fn format_err() {
-        assert!(format(10, 7).is_err());
+        assert!(format(&ubig!(10), 1).is_err());
+        assert!(format(&ubig!(10), 37).is_err());
     }
 
     #[test]
     fn format_expected() {
-        assert_eq!(format(10, 2), Ok(String::from("1010")));
-        assert_eq!(format(10, 8), Ok(String::from("12")));
-        assert_eq!(format(10, 10), Ok(String::from("10")));
-        assert_eq!(format(10, 16), Ok(String::from("a")));
+        assert_eq!(format(&ubig!(0), 16), Ok(String::from("0")));
+        assert_eq!(format(&ubig!(10), 2), Ok(String::from("1010")));
+        assert_eq!(format(&ubig!(10), 7), Ok(String::from("13")));
+        assert_eq!(format(&ubig!(10), 8), Ok(String::from("12")));
+        assert_eq!(format(&ubig!(10), 10), Ok(String::from("10")));
+        assert_eq!(format(&ubig!(10), 16), Ok(String::from("a")));
+        assert_eq!(format(&ubig!(35), 36), Ok(String::from("z")));
+    }
+
+    #[test]
+    fn format_beyond_u64() {
+        let n = ubig!(_0x10000000000000000);
+        assert_eq!(format(&n, 16), Ok(String::from("10000000000000000")));
     }
 
     #[test]
@@ -184,4 +362,84 @@ mod test {
         assert_eq!(convert("10", 10, 8), Ok(String::from("12")));
         assert_eq!(convert("10", 10, 16), Ok(String::from("a")));
     }
+
+    #[test]
+    fn format_twos_complement_expected() {
+        assert_eq!(format_twos_complement(-1, 16, 8), Ok(String::from("ff")));
+        assert_eq!(
+            format_twos_complement(-1, 16, 32),
+            Ok(String::from("ffffffff"))
+        );
+        assert_eq!(format_twos_complement(-1, 2, 8), Ok(String::from("11111111")));
+        assert_eq!(format_twos_complement(10, 16, 8), Ok(String::from("0a")));
+        assert_eq!(format_twos_complement(-10, 10, 8), Ok(String::from("246")));
+    }
+
+    #[test]
+    fn format_twos_complement_masks_to_width() {
+        // -300 truncated to 8 bits is 0xd4, not the full 128-bit pattern.
+        assert_eq!(format_twos_complement(-300, 16, 8), Ok(String::from("d4")));
+        assert_eq!(format_twos_complement(-1, 16, 128), Ok(String::from("f".repeat(32))));
+    }
+
+    #[test]
+    fn format_twos_complement_invalid_width() {
+        assert!(format_twos_complement(-1, 16, 0).is_err());
+        assert!(format_twos_complement(-1, 16, 129).is_err());
+    }
+
+    #[test]
+    fn convert_negative() {
+        assert_eq!(convert("-10", 10, 16), Ok(String::from("-a")));
+        assert_eq!(convert("+10", 10, 16), Ok(String::from("a")));
+        assert_eq!(convert("-0", 10, 16), Ok(String::from("0")));
+    }
+
+    #[test]
+    fn infer_expected() {
+        assert_eq!(infer("1010"), Some(10));
+        assert_eq!(infer("9"), Some(10));
+        assert_eq!(infer("17"), Some(10));
+        assert_eq!(infer("200"), Some(10));
+        assert_eq!(infer("8"), Some(10));
+        assert_eq!(infer("ff"), Some(16));
+        assert_eq!(infer("z"), Some(36));
+    }
+
+    #[test]
+    fn infer_none() {
+        assert_eq!(infer(""), None);
+        assert_eq!(infer("1.5"), None);
+        assert_eq!(infer("-5"), None);
+    }
+
+    #[test]
+    fn radix_from_str_names() {
+        assert_eq!("hex".parse::<Radix>(), Ok(Radix(16)));
+        assert_eq!("binary".parse::<Radix>(), Ok(Radix(2)));
+        assert_eq!("octal".parse::<Radix>(), Ok(Radix(8)));
+        assert_eq!("decimal".parse::<Radix>(), Ok(Radix(10)));
+    }
+
+    #[test]
+    fn radix_from_str_prefixes() {
+        assert_eq!("0x".parse::<Radix>(), Ok(Radix(16)));
+        assert_eq!("0b".parse::<Radix>(), Ok(Radix(2)));
+        assert_eq!("0".parse::<Radix>(), Ok(Radix(8)));
+    }
+
+    #[test]
+    fn radix_from_str_numeric() {
+        assert_eq!("16".parse::<Radix>(), Ok(Radix(16)));
+        assert_eq!("36".parse::<Radix>(), Ok(Radix(36)));
+        assert!("1".parse::<Radix>().is_err());
+        assert!("37".parse::<Radix>().is_err());
+        assert!("nonsense".parse::<Radix>().is_err());
+    }
+
+    #[test]
+    fn radix_display() {
+        assert_eq!(Radix(16).to_string(), "hex");
+        assert_eq!(Radix(20).to_string(), "radix-20");
+    }
 }